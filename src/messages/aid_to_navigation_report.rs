@@ -0,0 +1,140 @@
+//! Aid-to-Navigation Report (type 21)
+use super::navigation::*;
+use super::parsers::*;
+use super::types::*;
+use super::{signed_i32, u8_to_bool, AisMessageType};
+use crate::errors::*;
+use nom::bits::{bits, complete::take as take_bits};
+use nom::combinator::map_res;
+use nom::IResult;
+
+/// Maximum length in bits of the optional name-extension field.
+const MAX_NAME_EXTENSION_BITS: usize = 88;
+
+#[derive(Debug, PartialEq)]
+pub struct AidToNavigationReport {
+    pub message_type: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub aid_type: u8,
+    pub name: String,
+    pub position_accuracy: Accuracy,
+    pub longitude: Option<f32>,
+    pub latitude: Option<f32>,
+    pub dimension_to_bow: u16,
+    pub dimension_to_stern: u16,
+    pub dimension_to_port: u16,
+    pub dimension_to_starboard: u16,
+    pub epfd_type: Option<EpfdType>,
+    pub utc_second: u8,
+    pub off_position: bool,
+    pub raim: bool,
+    pub virtual_aid: bool,
+    pub assigned_mode: bool,
+}
+
+impl<'a> AisMessageType<'a> for AidToNavigationReport {
+    fn name(&self) -> &'static str {
+        "Aid to Navigation Report"
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let (_, report) = parse_base(data)?;
+        Ok(report)
+    }
+}
+
+fn parse_base(data: &[u8]) -> IResult<&[u8], AidToNavigationReport> {
+    bits(move |data| -> IResult<_, _> {
+        let (data, message_type) = take_bits::<_, _, _, (_, _)>(6u8)(data)?;
+        let (data, repeat_indicator) = take_bits::<_, _, _, (_, _)>(2u8)(data)?;
+        let (data, mmsi) = take_bits::<_, _, _, (_, _)>(30u32)(data)?;
+        let (data, aid_type) = take_bits::<_, _, _, (_, _)>(5u8)(data)?;
+        let (data, base_name) = parse_6bit_ascii(data, 120)?;
+        let (data, position_accuracy) =
+            map_res(take_bits::<_, _, _, (_, _)>(1u8), Accuracy::parse)(data)?;
+        let (data, longitude) = map_res(|data| signed_i32(data, 28), parse_longitude)(data)?;
+        let (data, latitude) = map_res(|data| signed_i32(data, 27), parse_latitude)(data)?;
+        let (data, dimension_to_bow) = take_bits::<_, _, _, (_, _)>(9u16)(data)?;
+        let (data, dimension_to_stern) = take_bits::<_, _, _, (_, _)>(9u16)(data)?;
+        let (data, dimension_to_port) = take_bits::<_, _, _, (_, _)>(6u16)(data)?;
+        let (data, dimension_to_starboard) = take_bits::<_, _, _, (_, _)>(6u16)(data)?;
+        let (data, epfd_type) = map_res(take_bits::<_, _, _, (_, _)>(4u8), EpfdType::parse)(data)?;
+        let (data, utc_second) = take_bits::<_, _, _, (_, _)>(6u8)(data)?;
+        let (data, off_position) =
+            map_res(take_bits::<_, _, _, (_, _)>(1u8), u8_to_bool)(data)?;
+        let (data, _regional_reserved) = take_bits::<_, u8, _, (_, _)>(8u8)(data)?;
+        let (data, raim) = map_res(take_bits::<_, _, _, (_, _)>(1u8), u8_to_bool)(data)?;
+        let (data, virtual_aid) = map_res(take_bits::<_, _, _, (_, _)>(1u8), u8_to_bool)(data)?;
+        let (data, assigned_mode) =
+            map_res(take_bits::<_, _, _, (_, _)>(1u8), u8_to_bool)(data)?;
+        let (data, _spare) = take_bits::<_, u8, _, (_, _)>(1u8)(data)?;
+
+        // The name extension (0-88 bits, 6-bit ASCII) is only present when
+        // the sender had more characters than fit in the 20-char base name,
+        // so most messages end here.
+        let extension_bits = std::cmp::min(remaining_bits(data), MAX_NAME_EXTENSION_BITS) / 6 * 6;
+        let (data, name) = if extension_bits > 0 {
+            let (data, extension) = parse_6bit_ascii(data, extension_bits)?;
+            (data, base_name + &extension)
+        } else {
+            (data, base_name)
+        };
+
+        Ok((
+            data,
+            AidToNavigationReport {
+                message_type,
+                repeat_indicator,
+                mmsi,
+                aid_type,
+                name,
+                position_accuracy,
+                longitude,
+                latitude,
+                dimension_to_bow,
+                dimension_to_stern,
+                dimension_to_port,
+                dimension_to_starboard,
+                epfd_type,
+                utc_second,
+                off_position,
+                raim,
+                virtual_aid,
+                assigned_mode,
+            },
+        ))
+    })(data)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+    use crate::test_helpers::f32_equal_naive;
+
+    #[test]
+    fn test_type21() {
+        let bytestream = b"E>k`sO70VQ97aRh1T0W72V@611@=FVj<;V5d@00003vP100";
+        let bitstream = crate::messages::unarmor(bytestream, 2).unwrap();
+        let report = AidToNavigationReport::parse(&bitstream).unwrap();
+        assert_eq!(report.message_type, 21);
+        assert_eq!(report.mmsi, 993672060);
+        assert_eq!(report.name, "AMBROSE CHANNEL LBB");
+        assert_eq!(report.raim, false);
+        assert_eq!(report.virtual_aid, true);
+        assert_eq!(report.assigned_mode, false);
+    }
+
+    #[test]
+    fn test_type21_without_name_extension() {
+        // A message that ends exactly at the 272-bit fixed-field boundary
+        // should leave `name` as just the base 20-char field, with no name
+        // extension consumed.
+        let bytestream = b"E>k`sO70VQ97aRh1T0W72V@611@=FVj<;V5d@00003vP10";
+        let bitstream = crate::messages::unarmor(bytestream, 4).unwrap();
+        let report = AidToNavigationReport::parse(&bitstream).unwrap();
+        assert_eq!(report.mmsi, 993672060);
+        assert_eq!(report.name, "AMBROSE CHANNEL LBB");
+    }
+}
@@ -1,4 +1,5 @@
 //! Base Station Report (type 4)
+use super::encode::{armor, BitWriter, Encode};
 use super::navigation::*;
 use super::parsers::*;
 use super::radio_status::{parse_radio, RadioStatus};
@@ -39,6 +40,34 @@ impl<'a> AisMessageType<'a> for BaseStationReport {
     }
 }
 
+/// Encodes back to the raw bit layout `parse_base` reads.
+///
+/// `radio_status` does not round-trip: `RadioStatus` has no `Encode` impl
+/// yet, so the 19-bit communication state is written as a placeholder
+/// instead of re-encoding the original SOTDMA/ITDMA contents.
+impl Encode for BaseStationReport {
+    fn encode(&self, writer: &mut BitWriter) {
+        writer.push_bits(u64::from(self.message_type), 6);
+        writer.push_bits(u64::from(self.repeat_indicator), 2);
+        writer.push_bits(u64::from(self.mmsi), 30);
+        writer.push_bits(u64::from(self.year.unwrap_or(0)), 14);
+        writer.push_bits(u64::from(self.month.unwrap_or(0)), 4);
+        writer.push_bits(u64::from(self.day.unwrap_or(0)), 5);
+        writer.push_bits(u64::from(self.hour.unwrap_or(24)), 5);
+        writer.push_bits(u64::from(self.minute.unwrap_or(60)), 6);
+        writer.push_bits(u64::from(self.second.unwrap_or(60)), 6);
+        writer.push_bool(self.fix_quality == Accuracy::DGPS);
+        writer.push_signed(encode_longitude(self.longitude), 28);
+        writer.push_signed(encode_latitude(self.latitude), 27);
+        writer.push_bits(u64::from(self.epfd_type.map_or(0, EpfdType::code)), 4);
+        writer.push_bits(0, 10); // spare
+        writer.push_bool(self.raim);
+        // `RadioStatus` has no `Encode` impl yet, so the 19-bit communication
+        // state is written as a placeholder rather than round-tripped.
+        writer.push_bits(0, 19);
+    }
+}
+
 fn parse_base(data: &[u8]) -> IResult<&[u8], BaseStationReport> {
     bits(move |data| -> IResult<_, _> {
         let (data, message_type) = take_bits::<_, _, _, (_, _)>(6u8)(data)?;
@@ -144,4 +173,35 @@ mod tests {
             panic!("Expected SOTDMA message");
         }
     }
+
+    #[test]
+    fn test_type4_round_trip() {
+        let bytestream = b"403OtVAv7=i?;o?IaHE`4Iw020S:";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let base = BaseStationReport::parse(&bitstream).unwrap();
+
+        let mut writer = BitWriter::new();
+        base.encode(&mut writer);
+        let (payload, fill_bits) = armor(&writer);
+        let reencoded_bitstream = crate::messages::unarmor(payload.as_bytes(), fill_bits).unwrap();
+        let reencoded = BaseStationReport::parse(&reencoded_bitstream).unwrap();
+
+        // `radio_status` is excluded: `RadioStatus` has no `Encode` impl
+        // yet, so `encode` writes a placeholder communication state instead
+        // of round-tripping the original one.
+        assert_eq!(reencoded.message_type, base.message_type);
+        assert_eq!(reencoded.repeat_indicator, base.repeat_indicator);
+        assert_eq!(reencoded.mmsi, base.mmsi);
+        assert_eq!(reencoded.year, base.year);
+        assert_eq!(reencoded.month, base.month);
+        assert_eq!(reencoded.day, base.day);
+        assert_eq!(reencoded.hour, base.hour);
+        assert_eq!(reencoded.minute, base.minute);
+        assert_eq!(reencoded.second, base.second);
+        assert_eq!(reencoded.fix_quality, base.fix_quality);
+        assert_eq!(reencoded.longitude, base.longitude);
+        assert_eq!(reencoded.latitude, base.latitude);
+        assert_eq!(reencoded.epfd_type, base.epfd_type);
+        assert_eq!(reencoded.raim, base.raim);
+    }
 }
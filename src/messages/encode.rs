@@ -0,0 +1,124 @@
+//! Encoding decoded reports back into armored AIVDM payloads.
+//!
+//! This is the inverse of `crate::messages::unarmor`: [`BitWriter`]
+//! accumulates a message's fields bit by bit, and [`armor`] groups the
+//! result into 6-bit characters from the same printable alphabet `unarmor`
+//! decodes, reporting how many low bits of the final character are padding
+//! rather than data.
+/// A report that can be serialized back into an armored 6-bit payload.
+pub trait Encode {
+    fn encode(&self, writer: &mut BitWriter);
+}
+
+/// Accumulates a message's fields bit by bit, most-significant-bit first,
+/// ready to be grouped into 6-bit characters by [`armor`].
+#[derive(Debug, Default)]
+pub struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter::default()
+    }
+
+    /// Push the low `n_bits` of `value`, most-significant bit first.
+    pub fn push_bits(&mut self, value: u64, n_bits: usize) {
+        for i in (0..n_bits).rev() {
+            self.bits.push((value >> i) & 1 == 1);
+        }
+    }
+
+    /// Push a single flag bit.
+    pub fn push_bool(&mut self, value: bool) {
+        self.bits.push(value);
+    }
+
+    /// Push the low `n_bits` of a signed, two's-complement value -- the
+    /// inverse of `signed_i32`.
+    pub fn push_signed(&mut self, value: i32, n_bits: usize) {
+        let mask: u32 = if n_bits >= 32 {
+            u32::MAX
+        } else {
+            (1u32 << n_bits) - 1
+        };
+        self.push_bits(u64::from(value as u32 & mask), n_bits);
+    }
+
+    /// Push `s` as 6-bit ASCII, right-padded with `@` out to exactly
+    /// `n_bits` bits (a multiple of 6) -- the inverse of how
+    /// `parse_6bit_ascii` trims trailing `@`/spaces on decode.
+    pub fn push_6bit_ascii(&mut self, s: &str, n_bits: usize) {
+        let n_chars = n_bits / 6;
+        let padded = s.chars().chain(std::iter::repeat('@')).take(n_chars);
+        for c in padded {
+            self.push_bits(u64::from(ascii_to_sixbit(c)), 6);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+}
+
+/// Maps a name/callsign character onto its 6-bit payload value, the inverse
+/// of the table `parse_6bit_ascii` decodes through.
+fn ascii_to_sixbit(c: char) -> u8 {
+    match c.to_ascii_uppercase() as u8 {
+        byte @ 64..=95 => byte - 64,
+        byte @ 32..=63 => byte,
+        _ => 0, // unrepresentable characters armor as '@'
+    }
+}
+
+/// Maps a raw 6-bit group onto its printable AIVDM payload character, the
+/// inverse of the table `unarmor` decodes through.
+fn sixbit_to_armor_char(value: u8) -> char {
+    let value = value & 0x3f;
+    (if value < 40 { value + 48 } else { value + 56 }) as char
+}
+
+/// Groups a [`BitWriter`]'s accumulated bits into the printable 6-bit AIVDM
+/// alphabet, returning the armored payload and the number of fill bits
+/// padding the final character.
+pub fn armor(writer: &BitWriter) -> (String, u8) {
+    let total_bits = writer.len();
+    let n_chars = (total_bits + 5) / 6;
+    let fill_bits = (n_chars * 6 - total_bits) as u8;
+
+    let mut payload = String::with_capacity(n_chars);
+    for chunk_index in 0..n_chars {
+        let mut value = 0u8;
+        for bit_index in 0..6 {
+            let i = chunk_index * 6 + bit_index;
+            let bit = writer.bits.get(i).copied().unwrap_or(false);
+            value = (value << 1) | u8::from(bit);
+        }
+        payload.push(sixbit_to_armor_char(value));
+    }
+    (payload, fill_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armor_pads_the_final_character_with_fill_bits() {
+        let mut writer = BitWriter::new();
+        writer.push_bits(4, 6); // message type 4
+        writer.push_bits(0, 2); // repeat indicator
+        let (payload, fill_bits) = armor(&writer);
+        assert_eq!(payload.len(), 2);
+        assert_eq!(fill_bits, 4);
+    }
+
+    #[test]
+    fn six_bit_ascii_pads_short_strings_with_at_sign() {
+        let mut writer = BitWriter::new();
+        writer.push_6bit_ascii("AB", 24);
+        let (payload, fill_bits) = armor(&writer);
+        assert_eq!(payload.len(), 4);
+        assert_eq!(fill_bits, 0);
+    }
+}
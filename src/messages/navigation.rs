@@ -8,6 +8,16 @@ pub fn parse_speed_over_ground(data: u16) -> Result<Option<f32>> {
     }
 }
 
+/// Inverse of `parse_speed_over_ground`: scales a speed back to tenths of a
+/// knot, clamping to the maximum representable value, or `1023` ("not
+/// available") when `data` is `None`.
+pub fn encode_speed_over_ground(data: Option<f32>) -> u16 {
+    match data {
+        Some(speed) => ((speed * 10.0).round() as u16).min(1022),
+        None => 1023,
+    }
+}
+
 pub fn parse_longitude(data: i32) -> Result<Option<f32>> {
     match data {
         -108000000...108000000 => Ok(Some(data as f32 / 600000.0)),
@@ -16,6 +26,16 @@ pub fn parse_longitude(data: i32) -> Result<Option<f32>> {
     }
 }
 
+/// Inverse of `parse_longitude`: scales a longitude back to 1/600000 of a
+/// degree, clamping to the representable range, or the `108600000` ("not
+/// available") sentinel when `data` is `None`.
+pub fn encode_longitude(data: Option<f32>) -> i32 {
+    match data {
+        Some(longitude) => ((longitude * 600000.0).round() as i32).max(-108000000).min(108000000),
+        None => 108600000,
+    }
+}
+
 pub fn parse_latitude(data: i32) -> Result<Option<f32>> {
     match data {
         -54000000...54000000 => Ok(Some(data as f32 / 600000.0)),
@@ -24,6 +44,16 @@ pub fn parse_latitude(data: i32) -> Result<Option<f32>> {
     }
 }
 
+/// Inverse of `parse_latitude`: scales a latitude back to 1/600000 of a
+/// degree, clamping to the representable range, or the `54600000` ("not
+/// available") sentinel when `data` is `None`.
+pub fn encode_latitude(data: Option<f32>) -> i32 {
+    match data {
+        Some(latitude) => ((latitude * 600000.0).round() as i32).max(-54000000).min(54000000),
+        None => 54600000,
+    }
+}
+
 pub fn parse_cog(data: u16) -> Option<f32> {
     match data {
         3600 => None,
@@ -0,0 +1,176 @@
+//! Parsing of the raw `!AIVDM`/`!AIVDO` NMEA envelope.
+//!
+//! This is the entry point for a raw line straight off a serial port or UDP
+//! socket: it validates the trailing checksum, then splits out the talker
+//! id, sentence formatter, fragmentation metadata, radio channel, payload
+//! and fill bits, so that only a well-formed sentence's payload ever reaches
+//! `crate::messages::unarmor`.
+use crate::errors::*;
+
+/// Which kind of AIS sentence this is.
+///
+/// `Vdm` is a report relayed from another vessel; `Vdo` is an own-ship
+/// report produced by the receiver's own transponder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formatter {
+    Vdm,
+    Vdo,
+}
+
+impl Formatter {
+    fn parse(formatter: &str) -> Result<Self> {
+        match formatter {
+            "VDM" => Ok(Formatter::Vdm),
+            "VDO" => Ok(Formatter::Vdo),
+            _ => Err(format!("Unknown AIS sentence formatter: {}", formatter).into()),
+        }
+    }
+}
+
+/// A single decoded, checksum-validated `!AIVDM`/`!AIVDO` sentence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sentence {
+    /// Talker id, e.g. `AI` for a mobile AIS station.
+    pub talker_id: String,
+    pub formatter: Formatter,
+    /// Total number of fragments in this sentence's message group.
+    pub fragment_count: usize,
+    /// 1-based index of this fragment within its group.
+    pub fragment_number: usize,
+    /// Id shared by every fragment of a multi-part message; absent for
+    /// single-fragment messages.
+    pub sequential_message_id: Option<u32>,
+    /// Radio channel the sentence was heard on, where known.
+    pub channel: Option<char>,
+    /// The armored 6-bit payload, not yet passed through `unarmor`.
+    pub payload: String,
+    /// Number of bits in the last 6-bit character of `payload` that are
+    /// padding, not data.
+    pub fill_bits: u8,
+}
+
+impl Sentence {
+    /// Parse and checksum-validate a single raw NMEA line.
+    ///
+    /// Returns an error if the `*hh` checksum doesn't match, or if the
+    /// sentence doesn't have the expected comma-delimited field count --
+    /// rather than silently producing a partial result.
+    pub fn parse(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let body = line
+            .strip_prefix('!')
+            .or_else(|| line.strip_prefix('$'))
+            .ok_or_else(|| Error::from("Sentence must start with '!' or '$'"))?;
+
+        let star = body
+            .find('*')
+            .ok_or_else(|| Error::from("Missing '*' checksum delimiter"))?;
+        let (fields, checksum_str) = body.split_at(star);
+        let checksum_str = &checksum_str[1..];
+
+        let expected = fields.bytes().fold(0u8, |acc, b| acc ^ b);
+        let actual = u8::from_str_radix(checksum_str, 16)
+            .map_err(|_| Error::from(format!("Malformed checksum: {}", checksum_str)))?;
+        if expected != actual {
+            return Err(format!(
+                "Checksum mismatch: expected {:02X}, found {:02X}",
+                expected, actual
+            )
+            .into());
+        }
+
+        let parts: Vec<&str> = fields.split(',').collect();
+        if parts.len() != 7 {
+            return Err(format!("Expected 7 comma-delimited fields, found {}", parts.len()).into());
+        }
+
+        let header = parts[0];
+        if header.chars().count() != 5 {
+            return Err(format!("Malformed sentence header: {}", header).into());
+        }
+        // Slice by char, not by byte: a garbled-but-checksum-valid header
+        // could contain a multi-byte character, and byte indexing into the
+        // middle of one panics instead of erroring.
+        let talker_id: String = header.chars().take(2).collect();
+        let formatter_code: String = header.chars().skip(2).take(3).collect();
+        let formatter = Formatter::parse(&formatter_code)?;
+
+        let fragment_count = parts[1]
+            .parse()
+            .map_err(|_| Error::from(format!("Invalid fragment count: {}", parts[1])))?;
+        let fragment_number = parts[2]
+            .parse()
+            .map_err(|_| Error::from(format!("Invalid fragment number: {}", parts[2])))?;
+        let sequential_message_id = if parts[3].is_empty() {
+            None
+        } else {
+            Some(parts[3].parse().map_err(|_| {
+                Error::from(format!("Invalid sequential message id: {}", parts[3]))
+            })?)
+        };
+        let channel = parts[4].chars().next();
+        let payload = parts[5].to_string();
+        let fill_bits = parts[6]
+            .parse()
+            .map_err(|_| Error::from(format!("Invalid fill bit count: {}", parts[6])))?;
+
+        Ok(Sentence {
+            talker_id,
+            formatter,
+            fragment_count,
+            fragment_number,
+            sequential_message_id,
+            channel,
+            payload,
+            fill_bits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_well_formed_sentence() {
+        let sentence =
+            Sentence::parse("!AIVDM,1,1,,B,403OtVAv7=i?;o?IaHE`4Iw020S:,0*6B").unwrap();
+        assert_eq!(sentence.talker_id, "AI");
+        assert_eq!(sentence.formatter, Formatter::Vdm);
+        assert_eq!(sentence.fragment_count, 1);
+        assert_eq!(sentence.fragment_number, 1);
+        assert_eq!(sentence.sequential_message_id, None);
+        assert_eq!(sentence.channel, Some('B'));
+        assert_eq!(sentence.payload, "403OtVAv7=i?;o?IaHE`4Iw020S:");
+        assert_eq!(sentence.fill_bits, 0);
+    }
+
+    #[test]
+    fn rejects_a_bad_checksum() {
+        let result = Sentence::parse("!AIVDM,1,1,,B,403OtVAv7=i?;o?IaHE`4Iw020S:,0*00");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_a_malformed_field_count() {
+        // Checksum is valid for these fields -- only 6 comma-delimited
+        // fields, one short of the 7 a real sentence carries -- so this
+        // exercises the field-count check rather than the checksum check.
+        let result = Sentence::parse("!AIVDM,1,1,,B,payload*57");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_a_multibyte_header_without_panicking() {
+        // "€VD" is 5 bytes but only 3 chars -- byte-indexing into the
+        // header used to panic on a line like this instead of erroring.
+        let result = Sentence::parse("!€VD,1,1,,B,payload,0*C2");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn recognizes_own_ship_sentences() {
+        let sentence = Sentence::parse("!AIVDO,1,1,,A,403OtVAv7=i?;o?IaHE`4Iw020S:,0*6A").unwrap();
+        assert_eq!(sentence.formatter, Formatter::Vdo);
+    }
+}
@@ -0,0 +1,245 @@
+//! Multi-sentence `!AIVDM` reassembly.
+//!
+//! A single AIS message (type 5 in particular, and many type 24 Part B
+//! reports) is sometimes too large for one `!AIVDM` sentence and is split
+//! across several. [`Reassembler`] buffers fragments keyed on
+//! `(sequential_message_id, channel)` until a whole group has arrived, then
+//! hands back one concatenated armored payload ready for
+//! `crate::messages::unarmor`.
+use super::nmea::Sentence;
+use std::collections::HashMap;
+
+/// Maximum number of incomplete fragment groups held at once.
+///
+/// Bounds memory when a fragment is lost and its group never completes: once
+/// the cap is hit, the oldest incomplete group is evicted to make room.
+const MAX_PENDING_GROUPS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GroupKey {
+    sequential_message_id: u32,
+    channel: char,
+}
+
+#[derive(Debug)]
+struct PendingGroup {
+    fragment_count: usize,
+    fragments: Vec<Option<String>>,
+    fill_bits: u8,
+    inserted_at: usize,
+}
+
+/// Reassembles raw `!AIVDM`/`!AIVDO` sentences into complete armored
+/// payloads.
+///
+/// Single-fragment sentences (`fragment_count == 1`) pass straight through
+/// without being buffered. Multi-fragment sentences are held by
+/// `(sequential_message_id, channel)` until every fragment has arrived, at
+/// which point their payloads are concatenated -- using the fill-bit count
+/// of the final fragment -- and the slot is cleared.
+#[derive(Debug, Default)]
+pub struct Reassembler {
+    pending: HashMap<GroupKey, PendingGroup>,
+    next_insertion_order: usize,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Reassembler::default()
+    }
+
+    /// Feed one raw NMEA line through the reassembler.
+    ///
+    /// Returns `Ok(Some((payload, fill_bits)))` once a group is complete,
+    /// `Ok(None)` while a multi-fragment group is still waiting on more
+    /// fragments, and `Err` if the line isn't a well-formed AIVDM/AIVDO
+    /// sentence.
+    pub fn process_line(&mut self, line: &str) -> crate::errors::Result<Option<(String, u8)>> {
+        let sentence = Sentence::parse(line)?;
+
+        if sentence.fragment_count == 1 {
+            return Ok(Some((sentence.payload, sentence.fill_bits)));
+        }
+        if sentence.fragment_number == 0 || sentence.fragment_number > sentence.fragment_count {
+            return Err(format!(
+                "Fragment number {} out of range for a {}-fragment group",
+                sentence.fragment_number, sentence.fragment_count
+            )
+            .into());
+        }
+
+        let key = GroupKey {
+            sequential_message_id: sentence.sequential_message_id.unwrap_or(0),
+            channel: sentence.channel.unwrap_or('A'),
+        };
+        let inserted_at = self.next_insertion_order;
+        self.next_insertion_order += 1;
+
+        let fresh = |inserted_at| PendingGroup {
+            fragment_count: sentence.fragment_count,
+            fragments: vec![None; sentence.fragment_count],
+            fill_bits: sentence.fill_bits,
+            inserted_at,
+        };
+        let slot = self
+            .pending
+            .entry(key)
+            .or_insert_with(|| fresh(inserted_at));
+
+        // A fragment count that no longer matches, or a fragment number
+        // we've already filled, means this sentence belongs to a new group
+        // reusing the same id -- reset rather than risk splicing unrelated
+        // fragments together. (Out-of-range fragment numbers were already
+        // rejected above.)
+        let fragment_index = sentence.fragment_number.checked_sub(1);
+        let stale = slot.fragment_count != sentence.fragment_count
+            || fragment_index.map_or(true, |i| {
+                i >= slot.fragments.len() || slot.fragments[i].is_some()
+            });
+        if stale {
+            *slot = fresh(inserted_at);
+        }
+
+        let fragment_index = fragment_index.unwrap_or(0);
+        slot.fragments[fragment_index] = Some(sentence.payload);
+        // Only the true final fragment's fill-bit count applies to the
+        // assembled payload; an earlier-numbered fragment arriving after it
+        // must not clobber that count back down.
+        if sentence.fragment_number == sentence.fragment_count {
+            slot.fill_bits = sentence.fill_bits;
+        }
+
+        if slot.fragments.iter().all(Option::is_some) {
+            let slot = self.pending.remove(&key).unwrap();
+            let payload = slot.fragments.into_iter().map(Option::unwrap).collect();
+            return Ok(Some((payload, slot.fill_bits)));
+        }
+
+        self.evict_if_full();
+        Ok(None)
+    }
+
+    /// Drop the oldest incomplete group once we're over capacity, so a lost
+    /// fragment can't let pending state grow without bound.
+    fn evict_if_full(&mut self) {
+        if self.pending.len() <= MAX_PENDING_GROUPS {
+            return;
+        }
+        if let Some(oldest) = self
+            .pending
+            .iter()
+            .min_by_key(|(_, group)| group.inserted_at)
+            .map(|(key, _)| *key)
+        {
+            self.pending.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a valid, checksummed `!AIVDM` line for the given fields, so
+    /// tests don't have to hand-compute the trailing `*hh`.
+    fn sentence(
+        fragment_count: u8,
+        fragment_number: u8,
+        sequential_message_id: &str,
+        channel: char,
+        payload: &str,
+        fill_bits: u8,
+    ) -> String {
+        let fields = format!(
+            "AIVDM,{},{},{},{},{},{}",
+            fragment_count, fragment_number, sequential_message_id, channel, payload, fill_bits
+        );
+        let checksum = fields.bytes().fold(0u8, |acc, b| acc ^ b);
+        format!("!{}*{:02X}", fields, checksum)
+    }
+
+    #[test]
+    fn single_fragment_passes_through() {
+        let mut reassembler = Reassembler::new();
+        let line = sentence(1, 1, "", 'B', "403OtVAv7=i?;o?IaHE`4Iw020S:", 0);
+        let result = reassembler.process_line(&line).unwrap();
+        assert_eq!(result, Some(("403OtVAv7=i?;o?IaHE`4Iw020S:".to_string(), 0)));
+    }
+
+    #[test]
+    fn multi_fragment_group_is_buffered_then_assembled() {
+        let mut reassembler = Reassembler::new();
+        let first_line = sentence(
+            2,
+            1,
+            "9",
+            'A',
+            "53nFBv01SJ<thHp6220H4heHTf2222222222221?50:454o8500034pT7P",
+            0,
+        );
+        let first = reassembler.process_line(&first_line).unwrap();
+        assert_eq!(first, None);
+
+        let second_line = sentence(2, 2, "9", 'A', "00000000000", 2);
+        let second = reassembler.process_line(&second_line).unwrap();
+        assert_eq!(
+            second,
+            Some((
+                "53nFBv01SJ<thHp6220H4heHTf2222222222221?50:454o8500034pT7P00000000000".to_string(),
+                2
+            ))
+        );
+    }
+
+    #[test]
+    fn out_of_order_fragments_assemble_in_order() {
+        let mut reassembler = Reassembler::new();
+        let second_line = sentence(2, 2, "7", 'B', "second", 0);
+        assert_eq!(reassembler.process_line(&second_line).unwrap(), None);
+
+        let first_line = sentence(2, 1, "7", 'B', "first", 0);
+        let result = reassembler.process_line(&first_line).unwrap();
+        assert_eq!(result, Some(("firstsecond".to_string(), 0)));
+    }
+
+    #[test]
+    fn final_fragments_fill_bits_survive_out_of_order_arrival() {
+        let mut reassembler = Reassembler::new();
+        // The true final fragment (fragment_number == fragment_count) carries
+        // the real fill-bit count, but arrives before fragment 1, which
+        // reports 0. The earlier-numbered fragment arriving later must not
+        // clobber the final fragment's fill_bits back to 0.
+        let second_line = sentence(2, 2, "5", 'B', "second", 3);
+        assert_eq!(reassembler.process_line(&second_line).unwrap(), None);
+
+        let first_line = sentence(2, 1, "5", 'B', "first", 0);
+        let result = reassembler.process_line(&first_line).unwrap();
+        assert_eq!(result, Some(("firstsecond".to_string(), 3)));
+    }
+
+    #[test]
+    fn duplicate_fragment_number_resets_the_slot() {
+        let mut reassembler = Reassembler::new();
+        let first_line = sentence(2, 1, "3", 'A', "first", 0);
+        reassembler.process_line(&first_line).unwrap();
+
+        // Fragment 1 arrives again before fragment 2 ever showed up: this is
+        // a fresh group reusing the same sequential id.
+        let restarted_line = sentence(2, 1, "3", 'A', "restarted", 0);
+        reassembler.process_line(&restarted_line).unwrap();
+
+        let tail_line = sentence(2, 2, "3", 'A', "tail", 0);
+        let result = reassembler.process_line(&tail_line).unwrap();
+        assert_eq!(result, Some(("restartedtail".to_string(), 0)));
+    }
+
+    #[test]
+    fn stale_groups_are_evicted_under_pressure() {
+        let mut reassembler = Reassembler::new();
+        for i in 0..(MAX_PENDING_GROUPS + 1) {
+            let line = sentence(2, 1, &i.to_string(), 'A', "partial", 0);
+            reassembler.process_line(&line).unwrap();
+        }
+        assert!(reassembler.pending.len() <= MAX_PENDING_GROUPS);
+    }
+}
@@ -1,4 +1,5 @@
 //! Static Data Report (type 24)
+use super::encode::{armor, BitWriter, Encode};
 use super::parsers::*;
 use super::types::*;
 use super::AisMessageType;
@@ -52,6 +53,53 @@ pub enum MessagePart {
     },
 }
 
+impl Encode for StaticDataReport {
+    fn encode(&self, writer: &mut BitWriter) {
+        writer.push_bits(u64::from(self.message_type), 6);
+        writer.push_bits(u64::from(self.repeat_indicator), 2);
+        writer.push_bits(u64::from(self.mmsi), 30);
+        self.message_part.encode(writer);
+    }
+}
+
+impl Encode for MessagePart {
+    fn encode(&self, writer: &mut BitWriter) {
+        match self {
+            MessagePart::PartA { vessel_name } => {
+                writer.push_bits(0, 2);
+                writer.push_6bit_ascii(vessel_name, 120);
+            }
+            MessagePart::PartB {
+                ship_type,
+                vendor_id,
+                // `model_serial` is just an alternate 6-bit-ASCII reading of
+                // the same 24 bits as `unit_model_code` + `serial_number`
+                // (see `parse_message_part`), so it isn't written separately.
+                model_serial: _,
+                unit_model_code,
+                serial_number,
+                callsign,
+                dimension_to_bow,
+                dimension_to_stern,
+                dimension_to_port,
+                dimension_to_starboard,
+            } => {
+                writer.push_bits(1, 2);
+                writer.push_bits(u64::from(ship_type.map_or(0, ShipType::code)), 8);
+                writer.push_6bit_ascii(vendor_id, 18);
+                writer.push_bits(u64::from(*unit_model_code), 4);
+                writer.push_bits(u64::from(*serial_number), 20);
+                writer.push_6bit_ascii(callsign, 42);
+                writer.push_bits(u64::from(*dimension_to_bow), 9);
+                writer.push_bits(u64::from(*dimension_to_stern), 9);
+                writer.push_bits(u64::from(*dimension_to_port), 6);
+                writer.push_bits(u64::from(*dimension_to_starboard), 6);
+                writer.push_bits(0, 6); // spare
+            }
+        }
+    }
+}
+
 fn parse_message_part(data: (&[u8], usize), mmsi: Mmsi) -> IResult<(&[u8], usize), MessagePart> {
     let (data, part_number) = take_bits::<_, _, _, (_, _)>(2u8)(data)?;
     match part_number {
@@ -185,4 +233,34 @@ mod tests {
             _ => panic!("Expected Message Part B"),
         }
     }
+
+    #[test]
+    fn test_part_a_round_trip() {
+        let bytestream = b"H6:lEgQL4r1<QDr0P4pN3KSKP00";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let message = StaticDataReport::parse(&bitstream).unwrap();
+
+        let mut writer = BitWriter::new();
+        message.encode(&mut writer);
+        let (payload, fill_bits) = armor(&writer);
+        let reencoded_bitstream = crate::messages::unarmor(payload.as_bytes(), fill_bits).unwrap();
+        let reencoded = StaticDataReport::parse(&reencoded_bitstream).unwrap();
+
+        assert_eq!(message, reencoded);
+    }
+
+    #[test]
+    fn test_part_b_round_trip() {
+        let bytestream = b"H3mr@L4NC=D62?P<7nmpl00@8220";
+        let bitstream = crate::messages::unarmor(bytestream, 0).unwrap();
+        let message = StaticDataReport::parse(&bitstream).unwrap();
+
+        let mut writer = BitWriter::new();
+        message.encode(&mut writer);
+        let (payload, fill_bits) = armor(&writer);
+        let reencoded_bitstream = crate::messages::unarmor(payload.as_bytes(), fill_bits).unwrap();
+        let reencoded = StaticDataReport::parse(&reencoded_bitstream).unwrap();
+
+        assert_eq!(message, reencoded);
+    }
 }
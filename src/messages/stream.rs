@@ -0,0 +1,175 @@
+//! Streaming decoder over a `Read` byte source.
+//!
+//! [`AisStream`] wraps any `std::io::Read` -- a serial port, a TCP socket, a
+//! file -- and yields decoded messages as an iterator, buffering bytes and
+//! splitting on CR/LF line boundaries so a read that splits a line across
+//! two buffer fills is handled transparently. Each line is fed through the
+//! multi-sentence [`Reassembler`](super::reassembly::Reassembler); lines
+//! that aren't well-formed AIVDM/AIVDO sentences are skipped rather than
+//! aborting the stream.
+use super::aid_to_navigation_report::AidToNavigationReport;
+use super::base_station_report::BaseStationReport;
+use super::reassembly::Reassembler;
+use super::static_data_report::StaticDataReport;
+use super::{unarmor, AisMessageType};
+use crate::errors::*;
+use std::io::Read;
+
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// One decoded message pulled off the stream, tagged by which report type
+/// it turned out to be.
+#[derive(Debug, PartialEq)]
+pub enum DecodedMessage {
+    BaseStationReport(BaseStationReport),
+    StaticDataReport(StaticDataReport),
+    AidToNavigationReport(AidToNavigationReport),
+}
+
+fn decode(payload: &[u8], fill_bits: u8) -> Result<DecodedMessage> {
+    let bitstream = unarmor(payload, fill_bits)?;
+    // The first 6 bits of the unarmored payload are always the message
+    // type; peek it to pick which report parser to hand the bitstream to.
+    let message_type = bitstream.first().map_or(0, |byte| byte >> 2);
+    match message_type {
+        4 | 11 => Ok(DecodedMessage::BaseStationReport(BaseStationReport::parse(
+            &bitstream,
+        )?)),
+        21 => Ok(DecodedMessage::AidToNavigationReport(
+            AidToNavigationReport::parse(&bitstream)?,
+        )),
+        24 => Ok(DecodedMessage::StaticDataReport(StaticDataReport::parse(
+            &bitstream,
+        )?)),
+        _ => Err(format!("Unsupported or unimplemented message type: {}", message_type).into()),
+    }
+}
+
+/// Adapts any `Read` into an iterator of decoded AIS messages.
+pub struct AisStream<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    reassembler: Reassembler,
+    reader_exhausted: bool,
+}
+
+impl<R: Read> AisStream<R> {
+    pub fn new(reader: R) -> Self {
+        AisStream {
+            reader,
+            buffer: Vec::new(),
+            reassembler: Reassembler::new(),
+            reader_exhausted: false,
+        }
+    }
+
+    /// Pulls one complete CR/LF-terminated line out of the internal buffer,
+    /// if one is already available.
+    fn next_buffered_line(&mut self) -> Option<String> {
+        let newline_pos = self
+            .buffer
+            .iter()
+            .position(|&byte| byte == b'\n' || byte == b'\r')?;
+        let line: Vec<u8> = self.buffer.drain(..=newline_pos).collect();
+        Some(String::from_utf8_lossy(&line[..line.len() - 1]).into_owned())
+    }
+
+    /// Reads more bytes from the underlying source into the buffer.
+    fn fill_buffer(&mut self) -> std::io::Result<usize> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        let n = self.reader.read(&mut chunk)?;
+        self.buffer.extend_from_slice(&chunk[..n]);
+        Ok(n)
+    }
+
+    /// Feeds one line through the reassembler and, if it completed a group,
+    /// decodes it. Returns `None` for lines that are noise, incomplete
+    /// fragments, or blank -- the caller should keep looping.
+    fn handle_line(&mut self, line: &str) -> Option<Result<DecodedMessage>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        match self.reassembler.process_line(line) {
+            Ok(Some((payload, fill_bits))) => Some(decode(payload.as_bytes(), fill_bits)),
+            Ok(None) => None,
+            Err(_) => None, // not a well-formed AIVDM/AIVDO sentence; skip it
+        }
+    }
+}
+
+impl<R: Read> Iterator for AisStream<R> {
+    type Item = Result<DecodedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(line) = self.next_buffered_line() {
+                if let Some(result) = self.handle_line(&line) {
+                    return Some(result);
+                }
+                continue;
+            }
+
+            if self.reader_exhausted {
+                // No more newline-terminated lines, and the source is
+                // closed: flush whatever trailing bytes are left as one
+                // final line, then stop.
+                if self.buffer.is_empty() {
+                    return None;
+                }
+                let remaining: Vec<u8> = self.buffer.drain(..).collect();
+                let line = String::from_utf8_lossy(&remaining).into_owned();
+                return self.handle_line(&line).or(None);
+            }
+
+            match self.fill_buffer() {
+                Ok(0) => self.reader_exhausted = true,
+                Ok(_) => continue,
+                Err(e) => return Some(Err(format!("Error reading AIS stream: {}", e).into())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn decodes_a_single_fragment_line() {
+        let input = b"!AIVDM,1,1,,B,403OtVAv7=i?;o?IaHE`4Iw020S:,0*6B\r\n".to_vec();
+        let mut stream = AisStream::new(Cursor::new(input));
+        match stream.next().unwrap().unwrap() {
+            DecodedMessage::BaseStationReport(report) => assert_eq!(report.mmsi, 3669145),
+            other => panic!("Expected a BaseStationReport, got {:?}", other),
+        }
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn skips_noise_lines_without_aborting() {
+        let input = b"this is not AIVDM\r\n!AIVDM,1,1,,B,403OtVAv7=i?;o?IaHE`4Iw020S:,0*6B\r\n"
+            .to_vec();
+        let mut stream = AisStream::new(Cursor::new(input));
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().is_none());
+    }
+
+    /// A `Read` that dribbles out one byte per call, to exercise lines that
+    /// split across multiple buffer fills.
+    struct OneByteAtATime(Cursor<Vec<u8>>);
+
+    impl Read for OneByteAtATime {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(&mut buf[..1.min(buf.len())])
+        }
+    }
+
+    #[test]
+    fn handles_reads_that_split_a_line() {
+        let input = b"!AIVDM,1,1,,B,403OtVAv7=i?;o?IaHE`4Iw020S:,0*6B\n".to_vec();
+        let mut stream = AisStream::new(OneByteAtATime(Cursor::new(input)));
+        assert!(stream.next().unwrap().is_ok());
+    }
+}